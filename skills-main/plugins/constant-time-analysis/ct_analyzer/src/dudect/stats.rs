@@ -0,0 +1,134 @@
+//! Incremental mean/variance (Welford's algorithm) and Welch's two-sample
+//! t-test, used to decide whether the fixed and random input classes took
+//! measurably different time.
+
+/// Incrementally accumulates mean and variance over a stream of samples
+/// without storing them, using Welford's online algorithm.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Welford {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Welford {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, sample: f64) {
+        self.count += 1;
+        let delta = sample - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = sample - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Sample variance (Bessel-corrected). Returns `0.0` for fewer than two
+    /// samples.
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+}
+
+/// Outcome of comparing `|t|` against [`super::LEAK_THRESHOLD`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    ConstantTime,
+    Leaky,
+}
+
+/// Computes Welch's t-statistic for two independent samples with possibly
+/// unequal variance:
+///
+/// ```text
+/// t = (mean_a - mean_b) / sqrt(var_a / n_a + var_b / n_b)
+/// ```
+///
+/// Accumulates both samples' mean/variance via [`Welford`] rather than
+/// requiring them pre-aggregated, so callers can feed raw cycle counts
+/// directly.
+pub fn welch_t_test(a: &[u64], b: &[u64]) -> f64 {
+    let mut wa = Welford::new();
+    for &x in a {
+        wa.push(x as f64);
+    }
+    let mut wb = Welford::new();
+    for &x in b {
+        wb.push(x as f64);
+    }
+
+    if wa.count() < 2 || wb.count() < 2 {
+        return 0.0;
+    }
+
+    let se = (wa.variance() / wa.count() as f64 + wb.variance() / wb.count() as f64).sqrt();
+    if se == 0.0 {
+        return 0.0;
+    }
+    (wa.mean() - wb.mean()) / se
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welford_matches_hand_computed_mean_and_variance() {
+        let mut w = Welford::new();
+        for x in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            w.push(x);
+        }
+        assert_eq!(w.count(), 5);
+        assert_eq!(w.mean(), 3.0);
+        // Sample (Bessel-corrected) variance: sum((x-mean)^2) / (n-1)
+        // = (4+1+0+1+4) / 4 = 2.5
+        assert_eq!(w.variance(), 2.5);
+    }
+
+    #[test]
+    fn welford_variance_is_zero_for_fewer_than_two_samples() {
+        let mut w = Welford::new();
+        assert_eq!(w.variance(), 0.0);
+        w.push(42.0);
+        assert_eq!(w.variance(), 0.0);
+    }
+
+    #[test]
+    fn welch_t_test_matches_hand_computed_statistic() {
+        // mean_a=3, mean_b=4, var_a=var_b=2.5, n_a=n_b=5:
+        // se = sqrt(2.5/5 + 2.5/5) = 1.0, t = (3-4)/1.0 = -1.0
+        let a = [1u64, 2, 3, 4, 5];
+        let b = [2u64, 3, 4, 5, 6];
+        assert_eq!(welch_t_test(&a, &b), -1.0);
+        // Swapping the two samples negates t.
+        assert_eq!(welch_t_test(&b, &a), 1.0);
+    }
+
+    #[test]
+    fn welch_t_test_is_zero_with_fewer_than_two_samples_per_side() {
+        assert_eq!(welch_t_test(&[1], &[1, 2, 3]), 0.0);
+        assert_eq!(welch_t_test(&[1, 2, 3], &[]), 0.0);
+        assert_eq!(welch_t_test(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn welch_t_test_is_zero_for_identical_samples() {
+        // Both sides have var=0, so se==0 and welch_t_test must not divide
+        // by zero (which would otherwise produce NaN for equal means).
+        let a = [5u64, 5, 5, 5];
+        assert_eq!(welch_t_test(&a, &a), 0.0);
+    }
+}
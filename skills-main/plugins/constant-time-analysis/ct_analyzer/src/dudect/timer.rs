@@ -0,0 +1,30 @@
+//! Cycle-accurate timestamps for the dudect measurement loop.
+
+/// Reads the CPU's cycle counter (`RDTSC` on x86_64, the virtual counter
+/// register on aarch64). Not serialized against out-of-order execution --
+/// dudect's measurement loop amortizes that noise over many samples rather
+/// than paying for `RDTSCP`/`CPUID` fences on every iteration.
+#[cfg(target_arch = "x86_64")]
+pub fn cycles() -> u64 {
+    // SAFETY: `_rdtsc` is available on every x86_64 target we build for.
+    unsafe { std::arch::x86_64::_rdtsc() }
+}
+
+#[cfg(target_arch = "aarch64")]
+pub fn cycles() -> u64 {
+    let value: u64;
+    // SAFETY: reading a counter register has no side effects.
+    unsafe {
+        std::arch::asm!("mrs {}, cntvct_el0", out(reg) value, options(nomem, nostack));
+    }
+    value
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub fn cycles() -> u64 {
+    // Fall back to a monotonic clock; coarser than a cycle counter, but the
+    // t-test still reduces noise over enough samples.
+    use std::time::Instant;
+    static START: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
@@ -0,0 +1,95 @@
+//! Raw MemCheck client-request instrumentation.
+//!
+//! Valgrind client requests are a documented calling convention: load a
+//! request block into a fixed register, then execute a magic no-op sled that
+//! Valgrind's JIT recognizes and intercepts (real hardware just runs the
+//! no-ops and gets `default` back unchanged). This mirrors the `VALGRIND_*`
+//! macros in `valgrind/memcheck.h`; we only need the one request ctgrind
+//! relies on, `MAKE_MEM_UNDEFINED`.
+
+use std::arch::asm;
+
+/// `memcheck.h`'s `VG_USERREQ__MAKE_MEM_UNDEFINED`: one past
+/// `VG_USERREQ_TOOL_BASE('M', 'C')`, the first MemCheck request.
+const VG_USERREQ_MAKE_MEM_UNDEFINED: usize = (((b'M' as usize) << 24) | ((b'C' as usize) << 16)) + 1;
+
+/// A contiguous secret input buffer to mark undefined before the run.
+///
+/// Fields are private and [`Self::of_slice`] is the only constructor, so
+/// every `SecretRegion` is a `(ptr, len)` pair taken directly from a
+/// `&[u8]` that was live when it was built. The type carries no lifetime
+/// and is `Copy`, so it does not keep that slice alive: the caller must
+/// keep the buffer valid for as long as the region is used, which is the
+/// safety contract [`make_mem_undefined`] requires of its argument.
+#[derive(Debug, Clone, Copy)]
+pub struct SecretRegion {
+    ptr: *const u8,
+    len: usize,
+}
+
+impl SecretRegion {
+    pub fn of_slice(bytes: &[u8]) -> Self {
+        Self {
+            ptr: bytes.as_ptr(),
+            len: bytes.len(),
+        }
+    }
+
+    fn ptr(&self) -> *const u8 {
+        self.ptr
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Issues `VALGRIND_MAKE_MEM_UNDEFINED(region.ptr, region.len)`.
+///
+/// Under plain hardware execution (no Valgrind attached) this is a harmless
+/// no-op sled; under `valgrind --tool=memcheck` it clears the V-bits for the
+/// given range, so every byte in `region` is subsequently treated as
+/// undefined input. Any branch or address computation that later depends on
+/// those bytes is exactly the class of leak we want MemCheck to report.
+///
+/// # Safety
+/// `region.ptr` must be valid for `region.len` bytes for the duration of the
+/// call (the asm sled does not dereference it, but the Valgrind hypervisor
+/// does on the JIT side).
+pub unsafe fn make_mem_undefined(region: SecretRegion) {
+    request(VG_USERREQ_MAKE_MEM_UNDEFINED, region.ptr() as usize, region.len(), 0, 0);
+}
+
+/// Executes the four/five-no-op "magic sled" Valgrind pattern-matches on
+/// `x86_64`, with the request block `[request, a1, a2, a3, a4]` in `rax`'s
+/// pointee and the default return value pre-loaded in `rdx`. Valgrind reads
+/// a 6-element argument vector (the request plus 5 args); the 6th element is
+/// unused by `MAKE_MEM_UNDEFINED` but must still be present in the block.
+#[cfg(target_arch = "x86_64")]
+unsafe fn request(req: usize, a1: usize, a2: usize, a3: usize, a4: usize) -> usize {
+    let block: [usize; 6] = [req, a1, a2, a3, a4, 0];
+    let block_ptr = block.as_ptr();
+    let mut result: usize;
+    asm!(
+        "rol rdi, 3",
+        "rol rdi, 13",
+        "rol rdi, 61",
+        "rol rdi, 51",
+        "xchg rbx, rbx",
+        in("rax") block_ptr,
+        inout("rdx") 0usize => result,
+        in("rdi") block_ptr,
+        // The `rol` preamble clobbers CF/OF; Valgrind's own valgrind.h
+        // lists "cc" as clobbered, so the compiler must not assume flags
+        // survive this asm block.
+        options(nostack),
+    );
+    result
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+unsafe fn request(_req: usize, _a1: usize, _a2: usize, _a3: usize, _a4: usize) -> usize {
+    // The magic sled is architecture-specific; add aarch64/x86 variants here
+    // as the fixture set grows to cover those targets.
+    0
+}
@@ -0,0 +1,72 @@
+//! Runs an already-built test harness binary under
+//! `valgrind --tool=memcheck`; compiling the harness is not done here.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::finding::Finding;
+
+use super::report::parse_memcheck_xml;
+
+/// Drives one MemCheck run of a compiled harness binary.
+pub struct MemcheckHarness {
+    /// Path to the instrumented harness binary, built with the secret
+    /// regions poisoned via [`super::poison_secret`] before the call into
+    /// the function under test.
+    pub binary: PathBuf,
+    /// Extra arguments forwarded to the harness binary itself.
+    pub args: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum MemcheckHarnessError {
+    Spawn(std::io::Error),
+    /// Valgrind exited non-zero for a reason other than reporting errors
+    /// (missing binary, unsupported platform, etc).
+    ValgrindFailed { status: i32, stderr: String },
+    XmlParse(String),
+}
+
+impl MemcheckHarness {
+    pub fn new(binary: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: binary.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Runs the harness under MemCheck and returns one [`Finding`] per
+    /// uninitialized-value error MemCheck reports, mapped back to the
+    /// harness's source location where debug info allows it.
+    pub fn run(&self) -> Result<Vec<Finding>, MemcheckHarnessError> {
+        let xml_path = self.binary.with_extension("memcheck.xml");
+
+        let output = Command::new("valgrind")
+            .arg("--tool=memcheck")
+            .arg("--xml=yes")
+            .arg(format!("--xml-file={}", xml_path.display()))
+            // ctgrind's approach: undefined-value errors are exactly the
+            // conditional-branch/address leaks we're hunting for, so track
+            // them precisely rather than only on use.
+            .arg("--track-origins=yes")
+            .arg("--partial-loads-ok=no")
+            .arg(&self.binary)
+            .args(&self.args)
+            .output()
+            .map_err(MemcheckHarnessError::Spawn)?;
+
+        if !output.status.success() && !xml_path.exists() {
+            return Err(MemcheckHarnessError::ValgrindFailed {
+                status: output.status.code().unwrap_or(-1),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+
+        self.parse(&xml_path)
+    }
+
+    fn parse(&self, xml_path: &Path) -> Result<Vec<Finding>, MemcheckHarnessError> {
+        let xml = std::fs::read_to_string(xml_path).map_err(MemcheckHarnessError::Spawn)?;
+        parse_memcheck_xml(&xml).map_err(MemcheckHarnessError::XmlParse)
+    }
+}
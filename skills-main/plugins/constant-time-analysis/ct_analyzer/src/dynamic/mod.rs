@@ -0,0 +1,37 @@
+//! Dynamic taint-tracking backend, driven by Valgrind MemCheck.
+//!
+//! The static disassembly pass can only reason about the fixtures we ship
+//! (`decompose_vulnerable`, `use_hint_vulnerable`, ...); it has no notion of
+//! which *runtime* values are actually secret. This module closes that gap
+//! the way [ctgrind](https://github.com/agl/ctgrind) and
+//! [Timecop](https://github.com/veorq/timecop) do: we (ab)use MemCheck's
+//! existing uninitialized-memory propagation as a byte-precise data-flow
+//! tracker. A secret buffer is marked "undefined" via the client request
+//! protocol, the target runs under `valgrind --tool=memcheck`, and any
+//! conditional jump or memory access MemCheck reports as depending on
+//! undefined bytes is, by construction, a secret-dependent branch or
+//! variable-address access.
+//!
+//! Submodules:
+//! - [`client_request`]: emits the inline-asm MemCheck client requests.
+//! - [`harness`]: runs an already-built instrumented test harness under Valgrind.
+//! - [`report`]: parses MemCheck's `--xml=yes` output back into [`crate::finding::Finding`]s.
+
+mod client_request;
+mod harness;
+mod report;
+
+pub use client_request::SecretRegion;
+pub use harness::{MemcheckHarness, MemcheckHarnessError};
+pub use report::parse_memcheck_xml;
+
+/// Marks `region` as undefined for the lifetime of the current MemCheck run.
+///
+/// This is the public entry point analogous to ctgrind's
+/// `CTGRIND_MAKE_MEM_UNDEFINED`: call it on every secret input buffer at the
+/// top of the generated harness, before invoking the function under test.
+pub fn poison_secret(region: SecretRegion) {
+    // SAFETY: `region` must stay alive and valid for the duration of the
+    // MemCheck run; the caller (the generated harness) owns that buffer.
+    unsafe { client_request::make_mem_undefined(region) }
+}
@@ -0,0 +1,152 @@
+//! Parses MemCheck's machine-readable `--xml=yes` output into [`Finding`]s.
+//!
+//! We only care about the two error kinds that correspond to a
+//! secret-dependent branch or a secret-dependent memory address:
+//! `UninitCondition` (a conditional jump/move whose flags derive from
+//! undefined bytes -- exactly the `if r0 > gamma2` in `decompose_vulnerable`)
+//! and `UninitValue` when it occurs on a load/store address computation.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::finding::{Backend, Finding, Severity, SourceLocation};
+
+const LEAK_KINDS: &[&str] = &["UninitCondition", "UninitValue"];
+
+/// Parses one `valgrind --xml=yes` document and returns a [`Finding`] for
+/// each `<error>` whose `<kind>` indicates a use of undefined (i.e.
+/// secret-derived) data in a branch or address computation.
+pub fn parse_memcheck_xml(xml: &str) -> Result<Vec<Finding>, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut findings = Vec::new();
+    let mut buf = Vec::new();
+
+    let mut in_error = false;
+    let mut kind: Option<String> = None;
+    let mut first_frame: Option<SourceLocation> = None;
+    let mut first_frame_line_set = false;
+    let mut current_tag: Option<String> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf).map_err(|e| e.to_string())? {
+            Event::Eof => break,
+            Event::Start(tag) => {
+                let name = tag.name();
+                let name = String::from_utf8_lossy(name.as_ref()).into_owned();
+                if name == "error" {
+                    in_error = true;
+                    kind = None;
+                    first_frame = None;
+                    first_frame_line_set = false;
+                }
+                current_tag = Some(name);
+            }
+            Event::Text(text) if in_error => {
+                let text = text.unescape().map_err(|e| e.to_string())?.into_owned();
+                match current_tag.as_deref() {
+                    Some("kind") if kind.is_none() => kind = Some(text),
+                    Some("file") if first_frame.is_none() => {
+                        first_frame = Some(SourceLocation {
+                            file: text,
+                            line: 0,
+                            column: 0,
+                        });
+                    }
+                    // Only the first frame's own `<line>` belongs with the
+                    // `<file>` we just captured; every later frame in the
+                    // same `<error>` has its own `<file>`/`<line>` pair that
+                    // we don't track, so it must not overwrite this one.
+                    Some("line") if !first_frame_line_set => {
+                        if let Some(loc) = first_frame.as_mut() {
+                            loc.line = text.parse().unwrap_or(0);
+                            first_frame_line_set = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::End(tag) => {
+                let name = String::from_utf8_lossy(tag.name().as_ref()).into_owned();
+                if name == "error" {
+                    in_error = false;
+                    if let Some(kind) = kind.take() {
+                        if LEAK_KINDS.contains(&kind.as_str()) {
+                            let mut finding = Finding::new(
+                                Backend::Memcheck,
+                                Severity::Error,
+                                format!(
+                                    "MemCheck reported {kind}: a branch or memory access depends \
+                                     on data derived from a poisoned secret region"
+                                ),
+                            );
+                            if let Some(loc) = first_frame.take() {
+                                finding = finding.with_location(loc);
+                            }
+                            findings.push(finding);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_first_frame_file_and_line_survive_a_multi_frame_error() {
+        // Regression test for the bug fixed alongside the client-request
+        // block-size fix: a real `--xml=yes` <error> has one <frame> per
+        // stack level, each with its own <file>/<line>; only the innermost
+        // (first) frame -- here, the `if r0 > gamma2` branch in
+        // decompose_vulnerable -- should end up as the finding's location.
+        let xml = r#"
+        <valgrindoutput>
+          <error>
+            <kind>UninitCondition</kind>
+            <stack>
+              <frame>
+                <file>decompose_vulnerable.rs</file>
+                <line>30</line>
+              </frame>
+              <frame>
+                <file>main.rs</file>
+                <line>999</line>
+              </frame>
+            </stack>
+          </error>
+        </valgrindoutput>
+        "#;
+
+        let findings = parse_memcheck_xml(xml).expect("valid xml parses");
+        assert_eq!(findings.len(), 1);
+        let loc = findings[0].location.as_ref().expect("error has a location");
+        assert_eq!(loc.file, "decompose_vulnerable.rs");
+        assert_eq!(loc.line, 30, "a later frame's <line> must not overwrite the first frame's");
+    }
+
+    #[test]
+    fn non_leak_kinds_are_filtered_out() {
+        let xml = r#"
+        <valgrindoutput>
+          <error>
+            <kind>Leak_DefinitelyLost</kind>
+            <stack>
+              <frame><file>alloc.rs</file><line>1</line></frame>
+            </stack>
+          </error>
+        </valgrindoutput>
+        "#;
+
+        let findings = parse_memcheck_xml(xml).expect("valid xml parses");
+        assert!(findings.is_empty(), "only UninitCondition/UninitValue should produce findings");
+    }
+}
@@ -0,0 +1,70 @@
+//! Shared types for reporting constant-time violations, regardless of which
+//! backend (static disassembly, dynamic taint tracking, statistical timing)
+//! produced them.
+
+use std::fmt;
+
+/// A location in the analyzed crate's source that a finding can be mapped
+/// back to, e.g. via DWARF line info or a `proc_macro2::Span`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// How confident the backend that raised the finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The backend has a plausible reason to flag this site but cannot prove
+    /// the operand is secret-dependent (e.g. a static heuristic).
+    Warning,
+    /// The backend observed or proved a genuine secret-dependent branch or
+    /// memory access (e.g. MemCheck reported an uninitialized-value use).
+    Error,
+}
+
+/// Which subsystem raised a finding, so results from multiple backends can
+/// be merged and deduplicated without losing provenance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Disassembly-based static analysis of the compiled artifact.
+    Static,
+    /// Valgrind MemCheck-driven dynamic taint tracking.
+    Memcheck,
+    /// dudect-style statistical timing measurement.
+    Dudect,
+    /// sidefuzz-style evolutionary input search.
+    Sidefuzz,
+}
+
+/// A single detected (or suspected) constant-time violation.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub backend: Backend,
+    pub location: Option<SourceLocation>,
+    pub message: String,
+    pub severity: Severity,
+}
+
+impl Finding {
+    pub fn new(backend: Backend, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            backend,
+            location: None,
+            message: message.into(),
+            severity,
+        }
+    }
+
+    pub fn with_location(mut self, location: SourceLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+}
@@ -0,0 +1,84 @@
+//! Candidate representation plus the mutation and crossover operators the
+//! genetic search applies to it each generation.
+
+use crate::fuzz::{CostModel, Witness};
+
+/// One candidate in the population: a pair of inputs whose measured cost
+/// difference is the fitness the search maximizes.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub input_a: Vec<u8>,
+    pub input_b: Vec<u8>,
+}
+
+impl Candidate {
+    pub fn new(input_a: Vec<u8>, input_b: Vec<u8>) -> Self {
+        Self { input_a, input_b }
+    }
+
+    /// Measures both inputs under `cost_model` and packages the result as a
+    /// [`Witness`].
+    pub fn score(&self, cost_model: &dyn CostModel) -> Witness {
+        Witness {
+            input_a: self.input_a.clone(),
+            input_b: self.input_b.clone(),
+            cost_a: cost_model.measure(&self.input_a),
+            cost_b: cost_model.measure(&self.input_b),
+        }
+    }
+}
+
+/// Single-point crossover: splices a random prefix of `a` onto a random
+/// suffix of `b`, independently for each side of the pair.
+pub fn crossover(a: &Candidate, b: &Candidate) -> Candidate {
+    Candidate::new(
+        splice(&a.input_a, &b.input_a),
+        splice(&a.input_b, &b.input_b),
+    )
+}
+
+fn splice(a: &[u8], b: &[u8]) -> Vec<u8> {
+    if a.is_empty() || b.is_empty() {
+        return a.to_vec();
+    }
+    let cut = fastrand::usize(0..a.len().min(b.len()));
+    let mut child = a[..cut].to_vec();
+    child.extend_from_slice(&b[cut..]);
+    child
+}
+
+/// Mutates a candidate in place: flips a random bit and, less often,
+/// splices in a random byte run, on each side of the pair. `input_len` caps
+/// growth so candidates stay a fixed, comparable size.
+pub fn mutate(candidate: &mut Candidate, input_len: usize) {
+    flip_random_bit(&mut candidate.input_a);
+    flip_random_bit(&mut candidate.input_b);
+
+    if fastrand::f64() < 0.1 {
+        splice_random_bytes(&mut candidate.input_a, input_len);
+    }
+    if fastrand::f64() < 0.1 {
+        splice_random_bytes(&mut candidate.input_b, input_len);
+    }
+}
+
+fn flip_random_bit(bytes: &mut [u8]) {
+    if bytes.is_empty() {
+        return;
+    }
+    let byte_index = fastrand::usize(0..bytes.len());
+    let bit = fastrand::u8(0..8);
+    bytes[byte_index] ^= 1 << bit;
+}
+
+fn splice_random_bytes(bytes: &mut Vec<u8>, input_len: usize) {
+    if bytes.is_empty() {
+        return;
+    }
+    let start = fastrand::usize(0..bytes.len());
+    let run_len = fastrand::usize(1..=(bytes.len() - start).max(1));
+    for byte in bytes.iter_mut().skip(start).take(run_len) {
+        *byte = fastrand::u8(..);
+    }
+    bytes.truncate(input_len);
+}
@@ -0,0 +1,282 @@
+//! sidefuzz-style evolutionary search for timing-divergent input pairs.
+//!
+//! The dudect harness tells you *whether* a function leaks, given input
+//! classes you already suspect are interesting. This module instead
+//! searches for the input pair itself: following
+//! [sidefuzz](https://github.com/phayes/sidefuzz), we evolve a population of
+//! `(input_a, input_b)` candidates with a genetic algorithm whose fitness is
+//! the measured cost difference between the two inputs, until that
+//! difference exceeds a threshold. The survivor is a concrete,
+//! reproducible witness -- e.g. a `(hint, r)` pair for `use_hint_vulnerable`
+//! whose two branches take a measurably different number of instructions --
+//! rather than only a pass/fail verdict.
+
+mod genome;
+
+pub use genome::Candidate;
+
+use crate::finding::{Backend, Finding, Severity};
+use crate::fuzz::genome::{crossover, mutate};
+
+/// Measures the execution cost of invoking the function under test on a
+/// single input. Implementations may back this with hardware performance
+/// counters, an instrumentation interpreter, or (as a noisier fallback) the
+/// cycle counter used by [`crate::dudect`].
+pub trait CostModel {
+    fn measure(&self, input: &[u8]) -> u64;
+}
+
+/// A witness: an input pair whose measured cost differs by at least the
+/// configured threshold, evidence of a timing side channel.
+#[derive(Debug, Clone)]
+pub struct Witness {
+    pub input_a: Vec<u8>,
+    pub input_b: Vec<u8>,
+    pub cost_a: u64,
+    pub cost_b: u64,
+}
+
+impl Witness {
+    pub fn divergence(&self) -> u64 {
+        self.cost_a.abs_diff(self.cost_b)
+    }
+}
+
+/// Configuration for one evolutionary search run.
+pub struct SearchConfig {
+    pub population_size: usize,
+    pub generations: usize,
+    /// Search stops early, returning a [`Witness`], once a candidate pair's
+    /// cost difference reaches this many units (instructions, cycles, ...
+    /// whatever the [`CostModel`] measures).
+    pub divergence_threshold: u64,
+    /// Fixed length of each generated input, in bytes.
+    pub input_len: usize,
+}
+
+/// Runs the genetic search described above against `cost_model`, seeding the
+/// initial population from `seed`. Returns the best [`Witness`] found,
+/// whether or not it crossed `config.divergence_threshold` -- check
+/// [`Witness::divergence`] against the threshold to tell the two cases
+/// apart.
+pub fn search(cost_model: &dyn CostModel, seed: &dyn Fn() -> Vec<u8>, config: &SearchConfig) -> Witness {
+    assert!(
+        config.population_size > 0,
+        "SearchConfig::population_size must be at least 1, got 0"
+    );
+
+    let mut population: Vec<Candidate> = (0..config.population_size)
+        .map(|_| Candidate::new(seed(), seed()))
+        .collect();
+
+    let mut best = best_witness(&population, cost_model);
+
+    for _ in 0..config.generations {
+        if best.divergence() >= config.divergence_threshold {
+            break;
+        }
+
+        population = next_generation(&population, cost_model, config.input_len);
+        let candidate_best = best_witness(&population, cost_model);
+        if candidate_best.divergence() > best.divergence() {
+            best = candidate_best;
+        }
+    }
+
+    best
+}
+
+/// Packages a [`search`] result as a [`Finding`] so a sidefuzz-detected
+/// divergent input pair can be merged/deduplicated with the static, MemCheck
+/// and dudect backends' output the way [`crate::finding`]'s design intends,
+/// rather than staying a bespoke [`Witness`] only this module understands.
+/// Returns `None` if `witness` never reached `divergence_threshold` -- a
+/// search that bottomed out below threshold is not itself a finding.
+pub fn report(witness: &Witness, divergence_threshold: u64) -> Option<Finding> {
+    if witness.divergence() < divergence_threshold {
+        return None;
+    }
+    Some(Finding::new(
+        Backend::Sidefuzz,
+        Severity::Error,
+        format!(
+            "sidefuzz search found an input pair diverging by {} cost units, reaching or \
+             exceeding divergence_threshold={divergence_threshold}: input_a={:?} (cost {}) vs \
+             input_b={:?} (cost {})",
+            witness.divergence(),
+            witness.input_a,
+            witness.cost_a,
+            witness.input_b,
+            witness.cost_b
+        ),
+    ))
+}
+
+/// Panics if `population` is empty -- callers must go through [`search`],
+/// which rejects `population_size == 0` up front, or [`next_generation`],
+/// which always returns a population the same size as its input.
+fn best_witness(population: &[Candidate], cost_model: &dyn CostModel) -> Witness {
+    population
+        .iter()
+        .map(|candidate| candidate.score(cost_model))
+        .max_by_key(Witness::divergence)
+        .expect("population is never empty")
+}
+
+/// Produces the next generation by truncation selection -- the top half of
+/// the population by divergence survives unchanged -- then fills the rest
+/// of the population with mutated crossovers of those survivors.
+fn next_generation(population: &[Candidate], cost_model: &dyn CostModel, input_len: usize) -> Vec<Candidate> {
+    let mut scored: Vec<(Candidate, u64)> = population
+        .iter()
+        .map(|c| (c.clone(), c.score(cost_model).divergence()))
+        .collect();
+    scored.sort_by_key(|(_, divergence)| std::cmp::Reverse(*divergence));
+
+    let survivors = (scored.len() / 2).max(1);
+    let parents: Vec<Candidate> = scored.into_iter().take(survivors).map(|(c, _)| c).collect();
+
+    let mut next = parents.clone();
+    while next.len() < population.len() {
+        let a = &parents[next.len() % parents.len()];
+        let b = &parents[(next.len() + 1) % parents.len()];
+        let mut child = crossover(a, b);
+        mutate(&mut child, input_len);
+        next.push(child);
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decompose_vulnerable_fixture;
+
+    #[test]
+    #[should_panic(expected = "population_size must be at least 1")]
+    fn search_rejects_zero_population_size() {
+        struct NoopCost;
+        impl CostModel for NoopCost {
+            fn measure(&self, _input: &[u8]) -> u64 {
+                0
+            }
+        }
+        let config = SearchConfig {
+            population_size: 0,
+            generations: 1,
+            divergence_threshold: 1,
+            input_len: 4,
+        };
+        search(&NoopCost, &|| vec![0u8; 4], &config);
+    }
+
+    #[test]
+    fn next_generation_preserves_population_size() {
+        struct ByteSumCost;
+        impl CostModel for ByteSumCost {
+            fn measure(&self, input: &[u8]) -> u64 {
+                input.iter().map(|&b| b as u64).sum()
+            }
+        }
+        fastrand::seed(1);
+        let population: Vec<Candidate> = (0..8)
+            .map(|_| Candidate::new(vec![0u8; 4], vec![0u8; 4]))
+            .collect();
+        let next = next_generation(&population, &ByteSumCost, 4);
+        assert_eq!(next.len(), population.len());
+        for candidate in &next {
+            assert_eq!(candidate.input_a.len(), 4, "crossover/mutate must preserve input_len");
+            assert_eq!(candidate.input_b.len(), 4, "crossover/mutate must preserve input_len");
+        }
+    }
+
+    #[test]
+    fn next_generation_never_loses_the_current_best_divergence() {
+        // next_generation keeps the top half of the scored population as
+        // survivors and only fills the rest with mutated crossovers, so the
+        // best divergence found so far can never regress across a
+        // generation.
+        struct FirstByteCost;
+        impl CostModel for FirstByteCost {
+            fn measure(&self, input: &[u8]) -> u64 {
+                input.first().copied().unwrap_or(0) as u64
+            }
+        }
+        fastrand::seed(7);
+        let cost_model = FirstByteCost;
+        let mut population: Vec<Candidate> = (0..6)
+            .map(|_| Candidate::new(vec![fastrand::u8(..)], vec![fastrand::u8(..)]))
+            .collect();
+
+        let mut best = best_witness(&population, &cost_model).divergence();
+        for _ in 0..10 {
+            population = next_generation(&population, &cost_model, 1);
+            let new_best = best_witness(&population, &cost_model).divergence();
+            assert!(new_best >= best, "divergence regressed: {new_best} < {best}");
+            best = new_best;
+        }
+    }
+
+    #[test]
+    fn search_against_decompose_vulnerable_finds_a_consistent_witness() {
+        // Exercise the evolutionary search against the actual motivating
+        // fixture rather than only a synthetic CostModel: the "cost" here
+        // is a stand-in for the data-dependent branch count a real
+        // instruction-level cost model would report for
+        // decompose_vulnerable's `if r0 > gamma2` centering branch.
+        // ML-DSA-87's gamma2, mirroring the fixture's private GAMMA2_87
+        // constant -- the fixture only exposes `decompose_vulnerable` as a
+        // function of an explicit `gamma2` parameter.
+        const GAMMA2_87: i32 = 261_888;
+
+        struct DecomposeBranchCost;
+        impl CostModel for DecomposeBranchCost {
+            fn measure(&self, input: &[u8]) -> u64 {
+                let mut bytes = [0u8; 4];
+                let n = input.len().min(4);
+                bytes[..n].copy_from_slice(&input[..n]);
+                let r = i32::from_le_bytes(bytes);
+                // The returned r0 is already centered, so the branch is
+                // visible only as the `r1 += 1` it applies to the quotient.
+                let (r1, _) = decompose_vulnerable_fixture::decompose_vulnerable(r, GAMMA2_87);
+                u64::from(r1 != r / (2 * GAMMA2_87))
+            }
+        }
+
+        fastrand::seed(42);
+        let config = SearchConfig {
+            population_size: 16,
+            generations: 20,
+            divergence_threshold: 1,
+            input_len: 4,
+        };
+        let witness = search(
+            &DecomposeBranchCost,
+            &|| (0..4).map(|_| fastrand::u8(..)).collect(),
+            &config,
+        );
+
+        assert_eq!(witness.input_a.len(), 4);
+        assert_eq!(witness.input_b.len(), 4);
+        // The witness's recorded costs must match recomputing the branch
+        // predicate directly from decompose_vulnerable on the same inputs.
+        assert_eq!(witness.cost_a, DecomposeBranchCost.measure(&witness.input_a));
+        assert_eq!(witness.cost_b, DecomposeBranchCost.measure(&witness.input_b));
+    }
+
+    #[test]
+    fn report_is_none_below_threshold_and_some_at_or_above_it() {
+        let witness = Witness {
+            input_a: vec![1, 2, 3],
+            input_b: vec![4, 5, 6],
+            cost_a: 10,
+            cost_b: 15,
+        };
+        assert!(report(&witness, 6).is_none());
+
+        let finding = report(&witness, 5).expect("divergence at threshold must produce a Finding");
+        assert_eq!(finding.backend, Backend::Sidefuzz);
+        assert_eq!(finding.severity, Severity::Error);
+        assert!(finding.message.contains('5'));
+    }
+}
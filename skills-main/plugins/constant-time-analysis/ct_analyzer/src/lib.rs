@@ -0,0 +1,23 @@
+//! Constant-time analysis backends for doppler.
+//!
+//! This crate hosts the analyses that doppler runs over a target crate to
+//! surface secret-dependent branches and memory accesses. `finding` defines
+//! the shared result type; everything else is a backend that produces
+//! `Finding`s by a different method (static disassembly/AST analysis,
+//! dynamic taint tracking under Valgrind, statistical timing measurement,
+//! ...).
+
+pub mod dudect;
+pub mod dynamic;
+pub mod finding;
+pub mod fuzz;
+pub mod static_analysis;
+
+/// The vulnerable fixtures every backend's doc comments cite as their
+/// motivating case, shared by the backends' own tests so each one is
+/// demonstrated against the real functions rather than only synthetic
+/// inputs/closures.
+#[cfg(test)]
+#[path = "../tests/test_samples/decompose_vulnerable.rs"]
+#[allow(dead_code)]
+pub(crate) mod decompose_vulnerable_fixture;
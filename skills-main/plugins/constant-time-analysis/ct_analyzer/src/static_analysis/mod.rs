@@ -0,0 +1,159 @@
+//! Static analysis passes that reason over a target crate's source/AST,
+//! as distinct from the [`crate::dynamic`] (runtime taint) and
+//! [`crate::dudect`]/[`crate::fuzz`] (measurement-based) backends.
+
+pub mod disasm;
+pub mod division;
+pub mod scan;
+pub mod uarch;
+
+pub use disasm::scan_instructions;
+pub use scan::{scan_and_suggest, scan_source};
+
+use crate::finding::{Backend, Finding, Severity, SourceLocation};
+use division::{MagicDivision, SignedMagicDivision};
+
+/// A `/` or `%` by a compile-time constant found on a secret-derived value,
+/// e.g. the `r / two_gamma2` in `decompose_vulnerable` (signed -- `r` is
+/// centered around zero and frequently negative) or the `(m+1)` reductions
+/// in `use_hint_vulnerable`.
+///
+/// [`scan::scan_source`] populates one of these per constant-divisor `/`/`%`
+/// it can fully resolve (dividend identifier, divisor literal, and bit
+/// width/signedness from an in-scope type annotation); pass the result to
+/// [`suggest_replacement`], or use [`scan::scan_and_suggest`] to do both at
+/// once.
+#[derive(Debug, Clone)]
+pub struct DivisionSite {
+    pub location: SourceLocation,
+    /// The dividend expression's source text, e.g. `"r"`.
+    pub dividend: String,
+    /// The divisor's magnitude; its sign is carried separately by
+    /// `divisor_negative` so an unsigned-domain divisor and a signed negative
+    /// divisor of the same magnitude aren't conflated.
+    pub divisor: u128,
+    /// Bit width of the dividend's type.
+    pub bits: u32,
+    pub is_modulo: bool,
+    /// Whether the dividend's type is signed, e.g. `true` for `r: i32` in
+    /// `decompose_vulnerable`. [`MagicDivision`] (unsigned) sign-extends a
+    /// negative dividend to a huge positive value before multiplying, which
+    /// is silently wrong for signed sites -- this flag picks
+    /// [`SignedMagicDivision`] instead whenever it's set.
+    pub signed: bool,
+    /// If `signed` is set, whether the divisor itself is negative (e.g. a
+    /// literal `-two_gamma2`). Ignored for unsigned sites.
+    pub divisor_negative: bool,
+}
+
+/// Derives the constant-time replacement for `site` and renders it as a
+/// [`Finding`], so a reported IDIV on secret data comes with a concrete
+/// fix rather than only a warning.
+pub fn suggest_replacement(site: &DivisionSite) -> Finding {
+    let op = if site.is_modulo { "%" } else { "/" };
+
+    let (rewrite, multiplier, shift) = if site.signed {
+        let divisor = if site.divisor_negative {
+            -(site.divisor as i128)
+        } else {
+            site.divisor as i128
+        };
+        let magic = SignedMagicDivision::for_divisor(divisor, site.bits);
+        let rewrite = if site.is_modulo {
+            magic.remainder_expr(&site.dividend)
+        } else {
+            magic.quotient_expr(&site.dividend)
+        };
+        (rewrite, magic.multiplier, magic.shift)
+    } else {
+        let magic = MagicDivision::for_divisor(site.divisor, site.bits);
+        let rewrite = if site.is_modulo {
+            magic.remainder_expr(&site.dividend)
+        } else {
+            magic.quotient_expr(&site.dividend)
+        };
+        (rewrite, magic.multiplier as i128, magic.shift)
+    };
+
+    let divisor_text = if site.signed && site.divisor_negative {
+        format!("-{}", site.divisor)
+    } else {
+        site.divisor.to_string()
+    };
+
+    Finding::new(
+        Backend::Static,
+        Severity::Warning,
+        format!(
+            "`{dividend} {op} {divisor}` compiles to a variable-latency IDIV on secret-derived \
+             data; replace with the constant-time multiply-shift `{rewrite}` (multiplier {m}, shift {s})",
+            dividend = site.dividend,
+            divisor = divisor_text,
+            m = multiplier,
+            s = shift,
+        ),
+    )
+    .with_location(site.location.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The same GAMMA2_87/decompose_vulnerable shape as
+    // `tests/test_samples/decompose_vulnerable.rs`, to check the rendered
+    // signed rewrite's *numbers* (not just the string shape tested in
+    // `division.rs`) against the actual vulnerable division it's meant to
+    // replace.
+    const Q: i32 = 8380417;
+    const GAMMA2_87: i32 = (Q - 1) / 32;
+
+    fn decompose_vulnerable(r: i32, gamma2: i32) -> (i32, i32) {
+        let two_gamma2 = 2 * gamma2;
+        let mut r1 = r / two_gamma2;
+        let mut r0 = r % two_gamma2;
+        if r0 > gamma2 {
+            r0 -= two_gamma2;
+            r1 += 1;
+        }
+        (r1, r0)
+    }
+
+    #[test]
+    fn suggested_replacement_matches_decompose_vulnerable_quotient_and_remainder() {
+        let two_gamma2 = 2 * GAMMA2_87;
+        let quotient_site = DivisionSite {
+            location: SourceLocation {
+                file: "decompose_vulnerable.rs".into(),
+                line: 25,
+                column: 0,
+            },
+            dividend: "r".into(),
+            divisor: two_gamma2 as u128,
+            bits: 32,
+            is_modulo: false,
+            signed: true,
+            divisor_negative: false,
+        };
+        let remainder_site = DivisionSite {
+            is_modulo: true,
+            ..quotient_site.clone()
+        };
+
+        let magic = division::SignedMagicDivision::for_divisor(two_gamma2 as i128, 32);
+        for r in [-100_i32, -12345, -1, 0, 1, 12345, 100_000, i32::MIN, i32::MAX] {
+            let (expected_r1, expected_r0) = decompose_vulnerable(r, GAMMA2_87);
+            assert_eq!(magic.quotient(r as i128), expected_r1 as i128, "quotient mismatch at r={r}");
+            let remainder = r as i128 - two_gamma2 as i128 * magic.quotient(r as i128);
+            assert_eq!(remainder, expected_r0 as i128, "remainder mismatch at r={r}");
+        }
+
+        // Rendering must not panic and must cite the signed multiply-shift,
+        // not silently fall back to the unsigned (wrong-for-negative-r) path.
+        // Only the signed renderer emits `i128` arithmetic.
+        let quotient_finding = suggest_replacement(&quotient_site);
+        assert!(quotient_finding.message.contains("multiply-shift") && quotient_finding.message.contains("i128"));
+        let remainder_finding = suggest_replacement(&remainder_site);
+        assert!(remainder_finding.message.contains("multiply-shift") && remainder_finding.message.contains("i128"));
+    }
+}
@@ -0,0 +1,295 @@
+//! Source-scanning pass that discovers [`DivisionSite`]s in target source.
+//!
+//! Walks a parsed file's AST looking for `/` and `%` expressions whose
+//! divisor is a compile-time integer literal (optionally negated). This does
+//! not run type inference -- it only resolves a dividend's signedness and
+//! bit width from an explicit type annotation already in scope (a function
+//! parameter or a `let` with a type ascription) -- so a dividend whose type
+//! isn't spelled out anywhere the scan can see is skipped rather than
+//! guessed at. Under-reporting a site is recoverable (a human or a later
+//! pass can still find it); reporting one with the wrong bit width or
+//! signedness would hand [`suggest_replacement`] a magic-number rewrite that
+//! silently computes the wrong answer.
+//!
+//! This only catches a literal divisor, not any constant-at-runtime one:
+//! [`decompose_vulnerable`](../../../tests/test_samples/decompose_vulnerable.rs)'s
+//! own `r / two_gamma2` divides by a local computed from the `gamma2`
+//! parameter, which is a constant only in the sense that it isn't
+//! secret-dependent -- a fact this pass has no way to know without either a
+//! taint model (which identifiers carry secret data) or interprocedural
+//! constant propagation (whether `gamma2` is always called with the same
+//! literal), neither of which is in scope here. Closing that gap is future
+//! work; this pass still finds every *literal*-divisor site, which is the
+//! common case for fixed protocol constants spelled out inline.
+
+use std::collections::HashMap;
+
+use quote::ToTokens;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, ExprBinary, FnArg, ItemFn, Lit, Local, Pat, Type, UnOp};
+
+use crate::finding::SourceLocation;
+
+use super::{suggest_replacement, DivisionSite};
+
+/// Parses `source` (the text of one Rust source file) and returns one
+/// [`DivisionSite`] per constant-divisor `/`/`%` the scan can fully resolve,
+/// with [`SourceLocation::file`] set to `file`.
+pub fn scan_source(source: &str, file: &str) -> syn::Result<Vec<DivisionSite>> {
+    let parsed = syn::parse_file(source)?;
+    let mut visitor = DivisionVisitor {
+        file: file.to_string(),
+        types: HashMap::new(),
+        sites: Vec::new(),
+    };
+    visitor.visit_file(&parsed);
+    Ok(visitor.sites)
+}
+
+/// Scans `source` like [`scan_source`] and renders each resolved site's
+/// constant-time rewrite via [`suggest_replacement`], so a caller can go
+/// straight from source text to a list of actionable findings. Sites with a
+/// 128-bit dividend are left out, since the magic-number derivations only
+/// cover widths up to 64 bits.
+pub fn scan_and_suggest(
+    source: &str,
+    file: &str,
+) -> syn::Result<Vec<crate::finding::Finding>> {
+    Ok(scan_source(source, file)?
+        .iter()
+        .filter(|site| site.bits <= 64)
+        .map(suggest_replacement)
+        .collect())
+}
+
+/// A dividend identifier's resolved `(signed, bits)`, from its nearest
+/// enclosing type annotation.
+#[derive(Clone, Copy)]
+struct TypeInfo {
+    signed: bool,
+    bits: u32,
+}
+
+struct DivisionVisitor {
+    file: String,
+    /// Identifier -> resolved type, populated from function parameters and
+    /// `let` bindings as the visitor walks into their enclosing scope.
+    /// Deliberately flat (no per-block shadowing): the fixtures this pass
+    /// targets never shadow a parameter, and conflating two same-named
+    /// locals in different scopes only risks a missed site, not a wrong one.
+    types: HashMap<String, TypeInfo>,
+    sites: Vec<DivisionSite>,
+}
+
+impl<'ast> Visit<'ast> for DivisionVisitor {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        for input in &node.sig.inputs {
+            if let FnArg::Typed(pat_type) = input {
+                if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                    if let Some(info) = type_info(&pat_type.ty) {
+                        self.types.insert(pat_ident.ident.to_string(), info);
+                    }
+                }
+            }
+        }
+        visit::visit_item_fn(self, node);
+    }
+
+    fn visit_local(&mut self, node: &'ast Local) {
+        if let Pat::Type(pat_type) = &node.pat {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                if let Some(info) = type_info(&pat_type.ty) {
+                    self.types.insert(pat_ident.ident.to_string(), info);
+                }
+            }
+        }
+        visit::visit_local(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast ExprBinary) {
+        let is_modulo = matches!(node.op, BinOp::Rem(_));
+        if is_modulo || matches!(node.op, BinOp::Div(_)) {
+            if let Some(site) = self.site_for(node, is_modulo) {
+                self.sites.push(site);
+            }
+        }
+        visit::visit_expr_binary(self, node);
+    }
+}
+
+impl DivisionVisitor {
+    fn site_for(&self, node: &ExprBinary, is_modulo: bool) -> Option<DivisionSite> {
+        let (divisor, divisor_negative) = literal_divisor(&node.right)?;
+        let dividend = dividend_identifier(&node.left)?;
+        let info = self.types.get(&dividend)?;
+
+        let start = node.span().start();
+        Some(DivisionSite {
+            location: SourceLocation {
+                file: self.file.clone(),
+                line: start.line as u32,
+                column: start.column as u32,
+            },
+            dividend: node.left.to_token_stream().to_string(),
+            divisor,
+            bits: info.bits,
+            is_modulo,
+            signed: info.signed,
+            divisor_negative,
+        })
+    }
+}
+
+/// The simple identifier a division's dividend resolves to, if `expr` is (or
+/// is a parenthesized wrapper around) a bare path like `r`. Any
+/// more complex dividend expression has no single identifier whose type
+/// annotation this pass could look up, so it's left to a later pass.
+fn dividend_identifier(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        Expr::Paren(paren) => dividend_identifier(&paren.expr),
+        _ => None,
+    }
+}
+
+/// The divisor's `(magnitude, is_negative)` if `expr` is an integer literal,
+/// optionally wrapped in a unary `-`.
+fn literal_divisor(expr: &Expr) -> Option<(u128, bool)> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => Some((int.base10_parse::<u128>().ok()?, false)),
+            _ => None,
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            let (magnitude, _) = literal_divisor(&unary.expr)?;
+            Some((magnitude, true))
+        }
+        Expr::Paren(paren) => literal_divisor(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Maps a type annotation to `(signed, bits)` for the primitive integer
+/// types the fixtures use. `usize`/`isize` are treated as 64-bit, matching
+/// every target this crate runs against; a 32-bit target would need its own
+/// entry here rather than a guess.
+fn type_info(ty: &Type) -> Option<TypeInfo> {
+    let Type::Path(path) = ty else { return None };
+    let ident = path.path.get_ident()?.to_string();
+    let (signed, bits) = match ident.as_str() {
+        "i8" => (true, 8),
+        "i16" => (true, 16),
+        "i32" => (true, 32),
+        "i64" | "isize" => (true, 64),
+        "i128" => (true, 128),
+        "u8" => (false, 8),
+        "u16" => (false, 16),
+        "u32" => (false, 32),
+        "u64" | "usize" => (false, 64),
+        "u128" => (false, 128),
+        _ => return None,
+    };
+    Some(TypeInfo { signed, bits })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_signed_division_and_modulo_on_an_i32_parameter() {
+        let source = r#"
+            fn decompose_vulnerable(r: i32, gamma2: i32) -> (i32, i32) {
+                let two_gamma2 = 2 * gamma2;
+                let mut r1 = r / 523776;
+                let mut r0 = r % 523776;
+                if r0 > gamma2 {
+                    r0 -= two_gamma2;
+                    r1 += 1;
+                }
+                (r1, r0)
+            }
+        "#;
+        let sites = scan_source(source, "decompose_vulnerable.rs").expect("valid Rust source");
+        assert_eq!(sites.len(), 2, "expected exactly one / and one % site, got {sites:?}");
+
+        let quotient = sites.iter().find(|s| !s.is_modulo).expect("a quotient site");
+        assert_eq!(quotient.dividend, "r");
+        assert_eq!(quotient.divisor, 523776);
+        assert_eq!(quotient.bits, 32);
+        assert!(quotient.signed);
+        assert!(!quotient.divisor_negative);
+
+        let remainder = sites.iter().find(|s| s.is_modulo).expect("a remainder site");
+        assert_eq!(remainder.dividend, "r");
+        assert!(remainder.is_modulo);
+    }
+
+    #[test]
+    fn skips_division_by_a_non_constant() {
+        let source = r#"
+            fn f(a: i32, b: i32) -> i32 {
+                a / b
+            }
+        "#;
+        let sites = scan_source(source, "f.rs").expect("valid Rust source");
+        assert!(sites.is_empty(), "divisor is a variable, not a literal: {sites:?}");
+    }
+
+    #[test]
+    fn skips_sites_whose_dividend_type_is_not_in_scope() {
+        let source = r#"
+            fn f(r: SomeOpaqueType) -> SomeOpaqueType {
+                r / 7
+            }
+        "#;
+        let sites = scan_source(source, "f.rs").expect("valid Rust source");
+        assert!(sites.is_empty(), "dividend's type isn't a known integer primitive: {sites:?}");
+    }
+
+    #[test]
+    fn resolves_a_negative_literal_divisor_as_signed_and_negative() {
+        let source = r#"
+            fn f(r: i64) -> i64 {
+                r / -9
+            }
+        "#;
+        let sites = scan_source(source, "f.rs").expect("valid Rust source");
+        let site = sites.first().expect("one division site");
+        assert_eq!(site.divisor, 9);
+        assert!(site.divisor_negative);
+        assert!(site.signed);
+        assert_eq!(site.bits, 64);
+    }
+
+    #[test]
+    fn does_not_find_decompose_vulnerable_s_own_division_since_its_divisor_is_not_a_literal() {
+        // decompose_vulnerable divides by `two_gamma2`, a local computed at
+        // runtime from the `gamma2` parameter -- not a literal. Telling that
+        // divisor apart from a secret-dependent one (i.e. knowing gamma2 is
+        // always public) needs a taint/constant-propagation model this
+        // syntax-only pass deliberately doesn't have; document the gap with
+        // a test instead of silently missing it.
+        let source = include_str!("../../tests/test_samples/decompose_vulnerable.rs");
+        let sites = scan_source(source, "decompose_vulnerable.rs").expect("valid Rust source");
+        assert!(
+            sites.is_empty(),
+            "this pass only resolves literal divisors; a non-literal-divisor fixture catch would \
+             mean it started guessing at values it can't actually see, got {sites:?}"
+        );
+    }
+
+    #[test]
+    fn scan_and_suggest_renders_a_finding_for_each_resolved_site() {
+        let source = r#"
+            fn f(r: u32) -> u32 {
+                r / 5
+            }
+        "#;
+        let findings = scan_and_suggest(source, "f.rs").expect("valid Rust source");
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("multiply-shift"));
+        assert_eq!(findings[0].location.as_ref().unwrap().file, "f.rs");
+    }
+}
@@ -0,0 +1,202 @@
+//! Per-microarchitecture table of which instructions have operand-data-
+//! dependent latency.
+//!
+//! The fixtures hard-code the belief that `IDIV`, `DIVSD`/`FDIV`, and
+//! `SQRTSD`/`FSQRT` are variable-latency, but that is a property of the
+//! target core, not the instruction mnemonic in the abstract: some cores
+//! have fully pipelined, fixed-latency dividers, and some early-terminating
+//! multipliers or barrel shifters are themselves data-dependent. A
+//! disassembly-based static pass consults this table, keyed by the target
+//! [`Microarchitecture`], rather than assuming one blanket rule for every
+//! core: see [`super::disasm::scan_instructions`], which decodes an x86-64
+//! machine code stream with `iced-x86` and calls [`flag_instruction`] per
+//! decoded instruction.
+
+use std::collections::HashMap;
+
+use crate::finding::{Backend, Finding, Severity, SourceLocation};
+
+/// A target CPU family the instruction table can be keyed on. Variants are
+/// deliberately coarse (one per microarchitecture generation that actually
+/// changed divider/sqrt latency behavior), not one per SKU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Microarchitecture {
+    X86Skylake,
+    X86Zen3,
+    Aarch64NeoverseN1,
+    Aarch64AppleFirestorm,
+}
+
+/// Whether an instruction's latency depends on its operand values, on a
+/// given [`Microarchitecture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Latency {
+    /// Latency is fixed regardless of operand values.
+    Constant,
+    /// Latency varies with the operand values -- a potential timing leak
+    /// if any operand is secret-derived.
+    DataDependent,
+}
+
+/// Per-uarch map from instruction mnemonic to [`Latency`]. Construct one of
+/// the built-in tables with [`Self::builtin`], then layer `user_overrides`
+/// on top via [`Self::with_override`] for cores or microcode revisions the
+/// built-in data doesn't cover.
+#[derive(Debug, Clone)]
+pub struct InstructionLatencyTable {
+    uarch: Microarchitecture,
+    entries: HashMap<&'static str, Latency>,
+    user_overrides: HashMap<String, Latency>,
+}
+
+impl InstructionLatencyTable {
+    /// Looks up the known entries for `uarch`, per the built-in table below.
+    pub fn builtin(uarch: Microarchitecture) -> Self {
+        Self {
+            uarch,
+            entries: builtin_entries(uarch),
+            user_overrides: HashMap::new(),
+        }
+    }
+
+    /// Records a user-supplied classification for `mnemonic`, taking
+    /// priority over the built-in entry (e.g. because the user has
+    /// microbenchmarked their exact core/microcode revision).
+    pub fn with_override(mut self, mnemonic: impl Into<String>, latency: Latency) -> Self {
+        self.user_overrides
+            .insert(mnemonic.into().to_ascii_uppercase(), latency);
+        self
+    }
+
+    /// Classifies `mnemonic` (case-insensitive, e.g. `"divsd"` or `"DIVSD"`)
+    /// for this table's target uarch. A user override always takes
+    /// priority over the built-in entry. Returns `None` if the table has no
+    /// opinion, in which case the static pass should not report a finding
+    /// rather than guess.
+    pub fn classify(&self, mnemonic: &str) -> Option<Latency> {
+        let upper = mnemonic.to_ascii_uppercase();
+        self.user_overrides
+            .get(upper.as_str())
+            .or_else(|| self.entries.get(upper.as_str()))
+            .copied()
+    }
+
+    pub fn uarch(&self) -> Microarchitecture {
+        self.uarch
+    }
+}
+
+/// Consults `table` for `mnemonic` at `location` and, if it is classified as
+/// [`Latency::DataDependent`], returns a [`Finding`] citing which uarch
+/// entry triggered it -- e.g. "DIVSD has data-dependent latency on
+/// X86Skylake". Returns `None` for constant-latency or unclassified
+/// mnemonics.
+pub fn flag_instruction(
+    table: &InstructionLatencyTable,
+    mnemonic: &str,
+    location: SourceLocation,
+) -> Option<Finding> {
+    match table.classify(mnemonic) {
+        Some(Latency::DataDependent) => Some(
+            Finding::new(
+                Backend::Static,
+                Severity::Warning,
+                format!(
+                    "{mnemonic} has data-dependent latency on {uarch:?}; secret-derived operands \
+                     here are a timing leak on this target",
+                    uarch = table.uarch(),
+                ),
+            )
+            .with_location(location),
+        ),
+        _ => None,
+    }
+}
+
+/// The built-in variable-latency entries for `uarch`.
+///
+/// Every table flags the classic data-dependent integer divide,
+/// floating-point divide, and square root, under each ISA's own mnemonics:
+/// `IDIV`/`DIV`, `DIVSD`/`DIVSS` and `SQRTSD`/`SQRTSS` for the x86 cores,
+/// `SDIV`/`UDIV`, `FDIV` and `FSQRT` for the aarch64 cores. These remain
+/// operand-dependent on every mainstream core as of this writing. Per-core
+/// exceptions (e.g. a fully pipelined, fixed-latency
+/// divider) are expressed by omitting the entry, not by adding a `Constant`
+/// override, so an unclassified mnemonic still falls through to "no
+/// opinion" rather than a false negative masquerading as a verified
+/// constant-time result.
+fn builtin_entries(uarch: Microarchitecture) -> HashMap<&'static str, Latency> {
+    use Latency::DataDependent;
+
+    let mut entries = HashMap::new();
+    match uarch {
+        Microarchitecture::X86Skylake | Microarchitecture::X86Zen3 => {
+            entries.insert("IDIV", DataDependent);
+            entries.insert("DIV", DataDependent);
+            entries.insert("DIVSD", DataDependent);
+            entries.insert("DIVSS", DataDependent);
+            entries.insert("SQRTSD", DataDependent);
+            entries.insert("SQRTSS", DataDependent);
+        }
+        Microarchitecture::Aarch64NeoverseN1 | Microarchitecture::Aarch64AppleFirestorm => {
+            entries.insert("SDIV", DataDependent);
+            entries.insert("UDIV", DataDependent);
+            entries.insert("FDIV", DataDependent);
+            entries.insert("FSQRT", DataDependent);
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_is_case_insensitive_and_matches_builtin_entries() {
+        let table = InstructionLatencyTable::builtin(Microarchitecture::X86Skylake);
+        assert_eq!(table.classify("idiv"), Some(Latency::DataDependent));
+        assert_eq!(table.classify("IDIV"), Some(Latency::DataDependent));
+        assert_eq!(table.classify("DivSd"), Some(Latency::DataDependent));
+    }
+
+    #[test]
+    fn classify_returns_none_for_unclassified_mnemonic() {
+        let table = InstructionLatencyTable::builtin(Microarchitecture::X86Skylake);
+        // MOV is fixed-latency on every mainstream core; the table should
+        // have no opinion rather than default to either variant.
+        assert_eq!(table.classify("MOV"), None);
+    }
+
+    #[test]
+    fn aarch64_and_x86_tables_disagree_on_sdiv_udiv() {
+        let x86 = InstructionLatencyTable::builtin(Microarchitecture::X86Skylake);
+        let aarch64 = InstructionLatencyTable::builtin(Microarchitecture::Aarch64NeoverseN1);
+        assert_eq!(x86.classify("SDIV"), None);
+        assert_eq!(aarch64.classify("SDIV"), Some(Latency::DataDependent));
+    }
+
+    #[test]
+    fn user_override_takes_priority_over_builtin_entry() {
+        let table = InstructionLatencyTable::builtin(Microarchitecture::X86Skylake)
+            .with_override("idiv", Latency::Constant);
+        assert_eq!(table.classify("IDIV"), Some(Latency::Constant));
+    }
+
+    #[test]
+    fn flag_instruction_reports_a_finding_only_for_data_dependent_mnemonics() {
+        let table = InstructionLatencyTable::builtin(Microarchitecture::X86Skylake);
+        let location = SourceLocation {
+            file: "decompose_vulnerable.rs".into(),
+            line: 25,
+            column: 0,
+        };
+
+        let finding = flag_instruction(&table, "IDIV", location.clone())
+            .expect("IDIV is data-dependent on X86Skylake");
+        assert_eq!(finding.backend, Backend::Static);
+        assert!(finding.message.contains("IDIV"));
+
+        assert!(flag_instruction(&table, "MOV", location).is_none());
+    }
+}